@@ -2,8 +2,14 @@
 use wasm_bindgen::prelude::*;
 // Import Complex number type and FftPlanner from the rustfft crate
 use rustfft::{FftPlanner, num_complex::Complex};
+// Import the real-to-complex planner, used when the caller only has real
+// audio samples and doesn't need the redundant conjugate-symmetric half
+// of the spectrum
+use realfft::{RealFftPlanner, RealToComplex};
 // Import Arc for thread-safe reference counting
 use std::sync::Arc;
+// Import PI for the window coefficient formulas
+use std::f32::consts::PI;
 
 // Define a struct that will be exposed to JavaScript
 #[wasm_bindgen]
@@ -12,6 +18,57 @@ pub struct AudioProcessor {
     // Arc is used for thread-safe reference counting
     // dyn keyword is used for dynamic dispatch
     fft: Arc<dyn rustfft::Fft<f32>>,
+    // Store the matching inverse FFT so spectra can be transformed back to the time domain
+    fft_inverse: Arc<dyn rustfft::Fft<f32>>,
+    // Remember the transform size so the inverse can be normalized correctly
+    size: usize,
+    // Set when this processor was built via `new_real`, so `process_audio`
+    // takes the cheaper real-input path instead of the general complex one
+    real_fft: Option<Arc<dyn RealToComplex<f32>>>,
+    // Persistent complex working buffer for the general (non-real) path,
+    // reused every call instead of being reallocated
+    complex_buffer: Vec<Complex<f32>>,
+    // Persistent scratch space required by `fft.process_with_scratch`
+    scratch: Vec<Complex<f32>>,
+    // Persistent input/spectrum/scratch buffers for the real-to-complex path
+    real_input: Vec<f32>,
+    real_spectrum: Vec<Complex<f32>>,
+    real_scratch: Vec<Complex<f32>>,
+    // Persistent magnitude output buffer that `process_audio` writes into.
+    // JS reads this via `result_ptr`/`result_len` as a zero-copy Float32Array
+    // view instead of receiving a freshly allocated Vec every call
+    output: Vec<f32>,
+    // Precomputed per-sample window coefficients, multiplied into the signal
+    // before the transform to reduce spectral leakage. Defaults to
+    // rectangular (all 1.0, i.e. no windowing) until `set_window` is called
+    window: Vec<f32>,
+    // Number of frames produced by the most recent `process_spectrogram`
+    // call, so JS can reshape the flat row-major buffer it returned
+    last_frame_count: usize,
+}
+
+// Compute the coefficient table for a named analysis window of the given size.
+// Computed once per `set_window` call so the per-call cost in
+// `process_audio` is a single multiply per sample, with no transcendental
+// functions on the audio thread
+fn make_window(kind: &str, size: usize) -> Vec<f32> {
+    let n = size as f32;
+    match kind {
+        "hann" => (0..size)
+            .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / (n - 1.0)).cos())
+            .collect(),
+        "hamming" => (0..size)
+            .map(|i| 0.54 - 0.46 * (2.0 * PI * i as f32 / (n - 1.0)).cos())
+            .collect(),
+        "blackman" => (0..size)
+            .map(|i| {
+                let phase = 2.0 * PI * i as f32 / (n - 1.0);
+                0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos()
+            })
+            .collect(),
+        // "rectangular" and any unrecognized name fall back to no windowing
+        _ => vec![1.0; size],
+    }
 }
 
 // Implement methods for AudioProcessor that will be callable from JavaScript
@@ -25,23 +82,575 @@ impl AudioProcessor {
         let mut planner = FftPlanner::new();
         // Plan a forward FFT of the specified size
         let fft = planner.plan_fft_forward(size);
+        // Plan the matching inverse FFT so we can round-trip back to the time domain
+        let fft_inverse = planner.plan_fft_inverse(size);
+        // Pre-allocate the scratch and output buffers this processor will reuse
+        // for every call instead of allocating fresh ones per audio callback
+        let scratch = vec![Complex::new(0.0, 0.0); fft.get_inplace_scratch_len()];
+        let output = vec![0.0; size];
         // Return a new AudioProcessor instance
-        AudioProcessor { fft }
+        AudioProcessor {
+            fft,
+            fft_inverse,
+            size,
+            real_fft: None,
+            complex_buffer: vec![Complex::new(0.0, 0.0); size],
+            scratch,
+            real_input: Vec::new(),
+            real_spectrum: Vec::new(),
+            real_scratch: Vec::new(),
+            output,
+            window: vec![1.0; size],
+            last_frame_count: 0,
+        }
+    }
+
+    // Constructor that plans a real-to-complex transform instead of a full
+    // complex one. Real audio input produces a conjugate-symmetric spectrum,
+    // so only the `size/2 + 1` bins from DC to Nyquist carry information;
+    // `process_audio` on a processor built this way returns just those bins,
+    // roughly halving both compute and the data copied across the wasm/JS
+    // boundary for the common single-channel microphone use case.
+    #[wasm_bindgen]
+    pub fn new_real(size: usize) -> Self {
+        // Plan the complex transforms too, so process_spectrum/inverse keep working
+        let mut planner = FftPlanner::new();
+        let fft = planner.plan_fft_forward(size);
+        let fft_inverse = planner.plan_fft_inverse(size);
+
+        // Plan the real-to-complex forward transform for this size
+        let mut real_planner = RealFftPlanner::<f32>::new();
+        let real_fft = real_planner.plan_fft_forward(size);
+
+        // Pre-allocate every buffer the real-input path needs so process_audio
+        // never allocates once construction is done
+        let real_input = real_fft.make_input_vec();
+        let real_scratch = real_fft.make_scratch_vec();
+        let output = vec![0.0; size / 2 + 1];
+        let real_spectrum = real_fft.make_output_vec();
+
+        AudioProcessor {
+            fft,
+            fft_inverse,
+            size,
+            real_fft: Some(real_fft),
+            complex_buffer: Vec::new(),
+            scratch: Vec::new(),
+            real_input,
+            real_spectrum,
+            real_scratch,
+            output,
+            window: vec![1.0; size],
+            last_frame_count: 0,
+        }
+    }
+
+    // Set the analysis window applied to each frame before the transform.
+    // Accepts "hann", "hamming", "blackman", or "rectangular" (the default,
+    // i.e. no windowing). Recomputing the coefficient table here, once per
+    // change, keeps `process_audio` itself allocation-free.
+    #[wasm_bindgen]
+    pub fn set_window(&mut self, kind: &str) {
+        self.window = make_window(kind, self.size);
     }
 
     // Method to process audio data
     // This will be callable from JavaScript
+    //
+    // Writes magnitudes into the persistent `output` buffer in place instead
+    // of returning a freshly allocated Vec; call `result_ptr`/`result_len`
+    // afterwards to read them out as a zero-copy view over wasm memory
     #[wasm_bindgen]
-    pub fn process_audio(&self, audio_data: &[f32]) -> Vec<f32> {
-        // Convert the input audio data to complex numbers
-        // Real part is set to the audio sample, imaginary part is set to 0
+    pub fn process_audio(&mut self, audio_data: &[f32]) {
+        // The persistent buffers below are reused as in-place FFT scratch, so
+        // a short `audio_data` would otherwise leave their tail holding stale
+        // frequency-domain data from the previous call, which would then get
+        // silently re-transformed and exposed as if it were a valid result
+        assert_eq!(
+            audio_data.len(),
+            self.size,
+            "process_audio: audio_data length {} does not match configured size {}",
+            audio_data.len(),
+            self.size
+        );
+
+        // If this processor was built via `new_real`, take the cheaper
+        // real-input path that only computes and writes size/2 + 1 bins
+        if let Some(real_fft) = &self.real_fft {
+            // realfft mutates the input buffer as working space, so copy into
+            // the persistent input buffer rather than allocating a new one,
+            // applying the configured window along the way
+            for ((slot, &x), &w) in self.real_input.iter_mut().zip(audio_data.iter()).zip(self.window.iter()) {
+                *slot = x * w;
+            }
+            real_fft
+                .process_with_scratch(&mut self.real_input, &mut self.real_spectrum, &mut self.real_scratch)
+                .expect("real FFT size mismatch");
+
+            for (out, c) in self.output.iter_mut().zip(self.real_spectrum.iter()) {
+                *out = c.norm();
+            }
+            return;
+        }
+
+        // Copy the windowed input audio data into the persistent complex buffer
+        // Real part is set to the windowed audio sample, imaginary part is set to 0
+        for ((slot, &x), &w) in self.complex_buffer.iter_mut().zip(audio_data.iter()).zip(self.window.iter()) {
+            *slot = Complex::new(x * w, 0.0);
+        }
+
+        // Perform the FFT in place, reusing the pre-allocated scratch space
+        self.fft.process_with_scratch(&mut self.complex_buffer, &mut self.scratch);
+
+        // Write the magnitude of each complex bin into the persistent output buffer
+        for (out, c) in self.output.iter_mut().zip(self.complex_buffer.iter()) {
+            *out = c.norm();
+        }
+    }
+
+    // Pointer to the start of the persistent output buffer in wasm linear
+    // memory, so JavaScript can build a `Float32Array` view directly over it
+    // instead of copying the result across the boundary
+    #[wasm_bindgen]
+    pub fn result_ptr(&self) -> *const f32 {
+        self.output.as_ptr()
+    }
+
+    // Number of valid f32 values currently in the output buffer
+    #[wasm_bindgen]
+    pub fn result_len(&self) -> usize {
+        self.output.len()
+    }
+
+    // Method to get the full complex spectrum instead of just the magnitude
+    // Returns the real and imaginary parts interleaved as [re0, im0, re1, im1, ...]
+    // so callers that need phase (filtering, convolution, resynthesis) aren't stuck
+    // with the lossy magnitude-only output of `process_audio`
+    #[wasm_bindgen]
+    pub fn process_spectrum(&self, audio_data: &[f32]) -> Vec<f32> {
+        // Same mismatched-length guard as process_audio, so callers get this
+        // clear message instead of a raw rustfft panic
+        assert_eq!(
+            audio_data.len(),
+            self.size,
+            "process_spectrum: audio_data length {} does not match configured size {}",
+            audio_data.len(),
+            self.size
+        );
+
+        // Convert the input audio data to complex numbers; unlike process_audio,
+        // no analysis window is applied, so `inverse` reproduces the input exactly
         let mut complex_data: Vec<Complex<f32>> = audio_data.iter().map(|&x| Complex::new(x, 0.0)).collect();
-        
-        // Perform the FFT on the complex data
+
+        // Perform the forward FFT in place
         self.fft.process(&mut complex_data);
-        
-        // Convert the complex FFT result back to real numbers
-        // We take the magnitude (norm) of each complex number
-        complex_data.iter().map(|c| c.norm()).collect()
+
+        // Flatten the complex bins into an interleaved real/imaginary buffer
+        let mut spectrum = Vec::with_capacity(complex_data.len() * 2);
+        for c in complex_data {
+            spectrum.push(c.re);
+            spectrum.push(c.im);
+        }
+        spectrum
+    }
+
+    // Estimate the fundamental frequency (in Hz) of a signal via FFT-based
+    // autocorrelation, useful for instrument tuners and vocal feedback.
+    // Forward-FFTs the windowed signal, turns that into a power spectrum,
+    // inverse-FFTs the power spectrum to get the autocorrelation sequence,
+    // then searches the plausible pitch lag range for its peak and
+    // parabolically interpolates around it for sub-sample accuracy. Returns
+    // 0.0 when the normalized peak is too weak to trust, so silence or noise
+    // doesn't produce a bogus pitch.
+    #[wasm_bindgen]
+    pub fn detect_pitch(&self, audio_data: &[f32], sample_rate: f32) -> f32 {
+        // Same mismatched-length guard as process_audio, so callers get this
+        // clear message instead of a raw rustfft panic
+        assert_eq!(
+            audio_data.len(),
+            self.size,
+            "detect_pitch: audio_data length {} does not match configured size {}",
+            audio_data.len(),
+            self.size
+        );
+
+        // Apply the configured window and forward-FFT the signal
+        let mut complex_data: Vec<Complex<f32>> = audio_data
+            .iter()
+            .zip(self.window.iter())
+            .map(|(&x, &w)| Complex::new(x * w, 0.0))
+            .collect();
+        self.fft.process(&mut complex_data);
+
+        // Replace each bin with its power (re*re + im*im); the autocorrelation
+        // of the signal is the inverse FFT of its power spectrum
+        for c in complex_data.iter_mut() {
+            *c = Complex::new(c.re * c.re + c.im * c.im, 0.0);
+        }
+        self.fft_inverse.process(&mut complex_data);
+
+        let autocorr: Vec<f32> = complex_data.iter().map(|c| c.re).collect();
+        let zero_lag = autocorr[0];
+        if zero_lag <= 0.0 {
+            return 0.0;
+        }
+
+        // Plausible pitch range: 50 Hz to 2000 Hz, expressed as lag bounds.
+        // Both ends are clamped to the buffer so a small `size` relative to
+        // `sample_rate` (e.g. a low-latency tuner config) can't index out of
+        // bounds; if that leaves no valid lag to search, bail out with the
+        // documented "low confidence" result instead of panicking
+        let max_index = autocorr.len().saturating_sub(2);
+        let min_lag = ((sample_rate / 2000.0).floor() as usize).max(1).min(max_index);
+        let max_lag = ((sample_rate / 50.0).ceil() as usize).min(max_index);
+        if min_lag == 0 || min_lag > max_lag {
+            return 0.0;
+        }
+
+        let mut best_lag = min_lag;
+        let mut best_value = autocorr[min_lag];
+        if min_lag < max_lag {
+            for (offset, &value) in autocorr[min_lag + 1..=max_lag].iter().enumerate() {
+                if value > best_value {
+                    best_value = value;
+                    best_lag = min_lag + 1 + offset;
+                }
+            }
+        }
+
+        // Reject weak peaks (likely silence or noise rather than a pitched signal)
+        const CONFIDENCE_THRESHOLD: f32 = 0.01;
+        if best_value / zero_lag < CONFIDENCE_THRESHOLD {
+            return 0.0;
+        }
+
+        // Parabolic interpolation around the peak for sub-sample lag accuracy
+        let prev = autocorr[best_lag - 1];
+        let next = autocorr[best_lag + 1];
+        let denom = prev - 2.0 * best_value + next;
+        let refined_lag = if denom.abs() > f32::EPSILON {
+            best_lag as f32 + 0.5 * (prev - next) / denom
+        } else {
+            best_lag as f32
+        };
+
+        sample_rate / refined_lag
+    }
+
+    // Method to reconstruct a time-domain signal from an interleaved complex spectrum
+    // produced by `process_spectrum`
+    #[wasm_bindgen]
+    pub fn inverse(&self, spectrum: &[f32]) -> Vec<f32> {
+        // Same mismatched-length guard as process_audio: spectrum is size
+        // interleaved re/im pairs, so its length must be exactly 2 * size.
+        // `chunks_exact` would otherwise silently drop a trailing odd element
+        // and feed the wrong number of bins into self.fft_inverse
+        assert_eq!(
+            spectrum.len(),
+            self.size * 2,
+            "inverse: spectrum length {} does not match expected 2 * size ({})",
+            spectrum.len(),
+            self.size * 2
+        );
+
+        // Un-interleave the real/imaginary pairs back into complex numbers
+        let mut complex_data: Vec<Complex<f32>> = spectrum
+            .chunks_exact(2)
+            .map(|pair| Complex::new(pair[0], pair[1]))
+            .collect();
+
+        // Perform the inverse FFT in place
+        self.fft_inverse.process(&mut complex_data);
+
+        // rustfft's inverse transform is unnormalized, so divide by the transform
+        // size to get back the original amplitude and make the round trip exact
+        let scale = 1.0 / self.size as f32;
+        complex_data.iter().map(|c| c.re * scale).collect()
+    }
+
+    // Slide the FFT window across a longer input buffer in steps of `hop`
+    // samples, applying the configured window to each frame, and return all
+    // magnitude frames concatenated row-major. Doing the framing loop here
+    // instead of in JS avoids dozens of wasm/JS boundary crossings per
+    // scrolling-spectrogram update and lets the scratch buffer be reused
+    // across frames. Call `frame_count` afterwards to know how many rows of
+    // `size` bins each to reshape the flat result into.
+    #[wasm_bindgen]
+    pub fn process_spectrogram(&mut self, audio_data: &[f32], hop: usize) -> Vec<f32> {
+        let frame_count = if audio_data.len() >= self.size {
+            (audio_data.len() - self.size) / hop + 1
+        } else {
+            0
+        };
+        self.last_frame_count = frame_count;
+
+        // Scratch and frame buffers are allocated once and reused across every
+        // frame in this call, rather than per-frame
+        let mut magnitudes = Vec::with_capacity(frame_count * self.size);
+        let mut frame = vec![Complex::new(0.0, 0.0); self.size];
+        let mut scratch = vec![Complex::new(0.0, 0.0); self.fft.get_inplace_scratch_len()];
+        for frame_index in 0..frame_count {
+            let start = frame_index * hop;
+            let samples = &audio_data[start..start + self.size];
+
+            // Apply the configured window and forward-FFT this frame in place
+            for ((slot, &x), &w) in frame.iter_mut().zip(samples.iter()).zip(self.window.iter()) {
+                *slot = Complex::new(x * w, 0.0);
+            }
+            self.fft.process_with_scratch(&mut frame, &mut scratch);
+
+            magnitudes.extend(frame.iter().map(|c| c.norm()));
+        }
+        magnitudes
     }
-}
\ No newline at end of file
+
+    // Number of frames produced by the most recent `process_spectrogram`
+    // call, so JS can reshape the flat row-major result into a 2D image
+    #[wasm_bindgen]
+    pub fn frame_count(&self) -> usize {
+        self.last_frame_count
+    }
+}
+
+// Round up to the next power of two, used to pick the per-partition FFT size
+fn next_pow2(n: usize) -> usize {
+    n.next_power_of_two()
+}
+
+// Partitioned overlap-add FFT convolver for fast, real-time-safe convolution
+// of a streaming input against a fixed impulse response (IR). This is what
+// drives convolution reverb and arbitrary FIR filtering in the browser,
+// where a single direct-convolution pass over a multi-second IR would be far
+// too slow to run once per audio callback.
+//
+// The IR is split into partitions of `block_size` samples each, and every
+// partition is pre-transformed into an N-point spectrum (N = 2 * block_size
+// rounded up to a power of two, large enough that a linear convolution of two block_size-length signals
+// doesn't alias in the circular FFT convolution). Each call to
+// `process_block` forward-transforms only the new input block, multiplies
+// it against every IR partition paired with the correspondingly delayed
+// past input spectrum, sums the results, and inverse-transforms once,
+// carrying the tail forward via overlap-add.
+#[wasm_bindgen]
+pub struct Convolver {
+    // Size of the input/output blocks the caller feeds in and reads out
+    block_size: usize,
+    // FFT size used for every partition (2 * block_size rounded up to a
+    // power of two, so that a
+    // linear convolution of two block_size-length signals fits without
+    // circular aliasing)
+    fft_size: usize,
+    // Forward and inverse FFTs of `fft_size`, shared by the IR partitions
+    // and every incoming input block
+    fft: Arc<dyn rustfft::Fft<f32>>,
+    fft_inverse: Arc<dyn rustfft::Fft<f32>>,
+    // Pre-transformed IR partitions, oldest-to-newest-in-time as partition 0..N
+    ir_partitions: Vec<Vec<Complex<f32>>>,
+    // Ring of past input-block spectra, one slot per IR partition. The
+    // "most recent" slot moves as `ring_head` rotates, rather than the data
+    // itself being shifted; see `ring_head` for how the pairing works
+    input_spectra: Vec<Vec<Complex<f32>>>,
+    // Overlap tail carried from one block to the next (samples block_size..
+    // 2 * block_size of the previous block's inverse-FFT output)
+    overlap: Vec<f32>,
+    // Index of the most recently written slot in `input_spectra`. The ring
+    // rotates backwards on each call so that `input_spectra[(ring_head + i) %
+    // num_partitions]` is always the spectrum from `i` blocks ago, without
+    // ever shifting or reallocating the ring itself
+    ring_head: usize,
+    // Reused scratch buffers, so the only allocation in `process_block` is
+    // the returned output block
+    scratch: Vec<Complex<f32>>,
+    time_buffer: Vec<Complex<f32>>,
+    accumulator: Vec<Complex<f32>>,
+}
+
+#[wasm_bindgen]
+impl Convolver {
+    // Construct a convolver for the given impulse response and block size
+    #[wasm_bindgen(constructor)]
+    pub fn new(impulse_response: &[f32], block_size: usize) -> Self {
+        // An empty IR would leave `num_partitions` at zero, which later divides
+        // and mods `ring_head` by it in `process_block`; reject it here with a
+        // clear message instead of panicking on the first block
+        assert!(!impulse_response.is_empty(), "Convolver::new: impulse_response must not be empty");
+
+        let fft_size = next_pow2(block_size * 2);
+
+        let mut planner = FftPlanner::new();
+        let fft = planner.plan_fft_forward(fft_size);
+        let fft_inverse = planner.plan_fft_inverse(fft_size);
+        let scratch_len = fft.get_inplace_scratch_len().max(fft_inverse.get_inplace_scratch_len());
+
+        // Split the IR into block_size-sized partitions, zero-pad each to
+        // fft_size, and forward-FFT it once up front
+        let num_partitions = impulse_response.len().div_ceil(block_size);
+        let mut ir_partitions = Vec::with_capacity(num_partitions);
+        let mut partition_scratch = vec![Complex::new(0.0, 0.0); scratch_len];
+        for p in 0..num_partitions {
+            let start = p * block_size;
+            let end = (start + block_size).min(impulse_response.len());
+
+            let mut partition = vec![Complex::new(0.0, 0.0); fft_size];
+            for (slot, &x) in partition[..end - start].iter_mut().zip(&impulse_response[start..end]) {
+                *slot = Complex::new(x, 0.0);
+            }
+            fft.process_with_scratch(&mut partition, &mut partition_scratch);
+            ir_partitions.push(partition);
+        }
+
+        // The input spectrum ring starts out silent; one empty spectrum slot
+        // per IR partition
+        let input_spectra = vec![vec![Complex::new(0.0, 0.0); fft_size]; num_partitions];
+
+        Convolver {
+            block_size,
+            fft_size,
+            fft,
+            fft_inverse,
+            ir_partitions,
+            input_spectra,
+            overlap: vec![0.0; block_size],
+            ring_head: 0,
+            scratch: vec![Complex::new(0.0, 0.0); scratch_len],
+            time_buffer: vec![Complex::new(0.0, 0.0); fft_size],
+            accumulator: vec![Complex::new(0.0, 0.0); fft_size],
+        }
+    }
+
+    // Convolve one block_size-length block of streaming input against the
+    // impulse response and return the corresponding block_size-length
+    // output block
+    #[wasm_bindgen]
+    pub fn process_block(&mut self, input: &[f32]) -> Vec<f32> {
+        // Zero-pad the new block into the time buffer and forward-FFT it
+        for (slot, &x) in self.time_buffer[..self.block_size].iter_mut().zip(input.iter()) {
+            *slot = Complex::new(x, 0.0);
+        }
+        for slot in self.time_buffer[self.block_size..].iter_mut() {
+            *slot = Complex::new(0.0, 0.0);
+        }
+        self.fft.process_with_scratch(&mut self.time_buffer, &mut self.scratch);
+
+        // Rotate the ring backwards and overwrite the new head slot in place
+        // with the freshly computed spectrum: no clone, no insert/pop, no
+        // allocation, so this stays real-time safe on the audio thread
+        let num_partitions = self.input_spectra.len();
+        self.ring_head = (self.ring_head + num_partitions - 1) % num_partitions;
+        self.input_spectra[self.ring_head].copy_from_slice(&self.time_buffer);
+
+        // Multiply-accumulate every IR partition against its correspondingly
+        // delayed input spectrum, walking the ring forward from the head
+        for slot in self.accumulator.iter_mut() {
+            *slot = Complex::new(0.0, 0.0);
+        }
+        for (i, ir_partition) in self.ir_partitions.iter().enumerate() {
+            let input_spectrum = &self.input_spectra[(self.ring_head + i) % num_partitions];
+            for ((acc, ir), input) in self.accumulator.iter_mut().zip(ir_partition.iter()).zip(input_spectrum.iter()) {
+                *acc += ir * input;
+            }
+        }
+
+        // Inverse-FFT the accumulated spectrum and normalize by fft_size
+        self.fft_inverse.process_with_scratch(&mut self.accumulator, &mut self.scratch);
+        let scale = 1.0 / self.fft_size as f32;
+
+        // Emit the first block_size samples, adding in the overlap carried
+        // from the previous block, then carry the new tail forward
+        let mut output = vec![0.0; self.block_size];
+        for ((out, acc), &ov) in output
+            .iter_mut()
+            .zip(self.accumulator[..self.block_size].iter())
+            .zip(self.overlap.iter())
+        {
+            *out = acc.re * scale + ov;
+        }
+        for (ov, acc) in self.overlap.iter_mut().zip(self.accumulator[self.block_size..].iter()) {
+            *ov = acc.re * scale;
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn process_spectrum_inverse_roundtrips() {
+        let processor = AudioProcessor::new(8);
+        let input: Vec<f32> = (0..8).map(|i| i as f32).collect();
+        let spectrum = processor.process_spectrum(&input);
+        let output = processor.inverse(&spectrum);
+        for (a, b) in input.iter().zip(output.iter()) {
+            assert!((a - b).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn new_real_returns_half_plus_one_bins() {
+        let mut processor = AudioProcessor::new_real(8);
+        processor.process_audio(&[1.0; 8]);
+        assert_eq!(processor.result_len(), 8 / 2 + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match configured size")]
+    fn process_audio_rejects_mismatched_length() {
+        let mut processor = AudioProcessor::new(8);
+        processor.process_audio(&[0.0; 4]);
+    }
+
+    #[test]
+    fn process_audio_does_not_leak_stale_data_between_calls() {
+        let mut processor = AudioProcessor::new(8);
+        processor.process_audio(&[1.0; 8]);
+        processor.process_audio(&[0.0; 8]);
+
+        let result = unsafe { std::slice::from_raw_parts(processor.result_ptr(), processor.result_len()) };
+        assert!(result.iter().all(|&v| v == 0.0));
+    }
+
+    #[test]
+    fn set_window_changes_output_from_rectangular() {
+        let mut processor = AudioProcessor::new(8);
+        let input: Vec<f32> = (0..8).map(|i| i as f32).collect();
+
+        processor.process_audio(&input);
+        let rectangular: Vec<f32> =
+            unsafe { std::slice::from_raw_parts(processor.result_ptr(), processor.result_len()) }.to_vec();
+
+        processor.set_window("hann");
+        processor.process_audio(&input);
+        let windowed: Vec<f32> =
+            unsafe { std::slice::from_raw_parts(processor.result_ptr(), processor.result_len()) }.to_vec();
+
+        assert_ne!(rectangular, windowed);
+    }
+
+    #[test]
+    fn convolver_identity_impulse_passes_input_through() {
+        let mut convolver = Convolver::new(&[1.0], 4);
+        let input = vec![1.0, 2.0, 3.0, 4.0];
+        let output = convolver.process_block(&input);
+        for (a, b) in input.iter().zip(output.iter()) {
+            assert!((a - b).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn detect_pitch_does_not_panic_for_small_size() {
+        let processor = AudioProcessor::new(16);
+        let silence = vec![0.0; 16];
+        assert_eq!(processor.detect_pitch(&silence, 44100.0), 0.0);
+    }
+
+    #[test]
+    fn process_spectrogram_reports_matching_frame_count() {
+        let mut processor = AudioProcessor::new(4);
+        let input = vec![0.0; 10]; // size=4, hop=2 -> (10 - 4) / 2 + 1 = 4 frames
+        let magnitudes = processor.process_spectrogram(&input, 2);
+        assert_eq!(processor.frame_count(), 4);
+        assert_eq!(magnitudes.len(), 4 * 4);
+    }
+}